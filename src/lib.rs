@@ -3,7 +3,8 @@
 #[cfg(feature = "styled")]
 use anstyle::{AnsiColor, Color, Style};
 use log::{Level, LevelFilter, Metadata, Record};
-#[cfg(feature = "env")]
+#[cfg(feature = "styled")]
+use std::io::IsTerminal;
 use std::str::FromStr;
 use std::sync::Mutex;
 #[cfg(feature = "time")]
@@ -15,19 +16,212 @@ type Res<T> = Result<T, Box<dyn std::error::Error>>;
 
 struct Logsy(Mutex<LogsyConf>);
 
+/// Which console stream the non-file sink writes to. `File` output is
+/// configured separately via [`try_to_file`]; selecting it here just silences
+/// the console stream.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LogDestination {
+    Stdout,
+    Stderr,
+    File,
+}
+
+/// When to emit ANSI color on the console sink.
+#[cfg(feature = "styled")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColorChoice {
+    /// color only when the chosen stream is a terminal
+    Auto,
+    Always,
+    Never,
+}
+
+/// Returns whether the given console stream is attached to a terminal.
+#[cfg(feature = "styled")]
+fn stream_is_tty(dest: LogDestination) -> bool {
+    match dest {
+        LogDestination::Stdout => std::io::stdout().is_terminal(),
+        LogDestination::Stderr => std::io::stderr().is_terminal(),
+        LogDestination::File => false,
+    }
+}
+
+/// Output format for the file sink.
+#[cfg(feature = "file")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LogFormat {
+    /// the default `[ts LEVEL mod] msg` text rendering
+    Text,
+    /// one flat JSON object per line, for log collectors that parse JSON lines
+    Json,
+}
+
+/// Escapes a string for embedding in a JSON string literal without pulling in a
+/// serialization crate: quotes, backslashes and control characters only.
+#[cfg(feature = "file")]
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
 struct LogsyConf {
     installed: bool,
-    to_stderr: bool,
+    /// the console stream to write to, or `None` when the console sink is off
+    console: Option<LogDestination>,
+    /// whether the chosen console stream is a terminal, cached on selection
+    #[cfg(feature = "styled")]
+    console_is_tty: bool,
+    #[cfg(feature = "styled")]
+    color: ColorChoice,
     #[cfg(feature = "file")]
     to_file: Option<fs::File>,
+    #[cfg(feature = "file")]
+    file_path: Option<std::path::PathBuf>,
+    /// `(max_bytes, keep)` when size-based rotation is active
+    #[cfg(feature = "file")]
+    rotate: Option<(u64, usize)>,
+    /// running byte count of the live file, so rotation doesn't `stat` per line
+    #[cfg(feature = "file")]
+    file_len: u64,
+    /// the file sink's output format
+    #[cfg(feature = "file")]
+    format: LogFormat,
+    /// whether to suppress file lines already emitted this session
+    #[cfg(feature = "file")]
+    dedup: bool,
+    /// rendered file lines (minus the volatile timestamp) already written
+    #[cfg(feature = "file")]
+    seen: Option<std::collections::HashSet<String>>,
     level: Option<Level>,
+    /// per-target overrides, sorted longest-prefix-first so the first match wins
+    filters: Vec<(String, LevelFilter)>,
+}
+
+#[cfg(feature = "file")]
+impl LogsyConf {
+    /// Builds the archive path for `foo.log.{n}` next to the live file.
+    fn archive_path(base: &std::path::Path, n: usize) -> std::path::PathBuf {
+        let mut name = base.as_os_str().to_owned();
+        name.push(format!(".{n}"));
+        std::path::PathBuf::from(name)
+    }
+
+    /// Strips the volatile leading timestamp from a rendered file line, giving
+    /// a stable dedup key. The timestamp (present under `feature = "time"`) is a
+    /// single space-free token right after the opening `[`.
+    fn strip_ts(line: &str) -> String {
+        if let Some(rest) = line.strip_prefix('[')
+            && rest.starts_with(|c: char| c.is_ascii_digit())
+            && let Some(sp) = rest.find(' ')
+        {
+            format!("[{}", &rest[sp + 1..])
+        } else {
+            line.to_owned()
+        }
+    }
+
+    /// Shifts the existing archives, renames the live file to `foo.log.1` and
+    /// reopens a fresh, truncated `foo.log`, returning the new handle.
+    fn shift_and_reopen(path: &std::path::Path, keep: usize) -> Res<fs::File> {
+        // drop anything beyond the keep count, then shift the rest down
+        let _ = fs::remove_file(Self::archive_path(path, keep));
+        for n in (1..keep).rev() {
+            let src = Self::archive_path(path, n);
+            if src.exists() {
+                fs::rename(&src, Self::archive_path(path, n + 1))?;
+            }
+        }
+        if keep > 0 {
+            fs::rename(path, Self::archive_path(path, 1))?;
+        } else {
+            let _ = fs::remove_file(path);
+        }
+        Ok(fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)?)
+    }
+
+    /// Rotates the live file. Must be called while holding the `Mutex` so no
+    /// concurrent log call writes to a half-rotated file. On any failure it
+    /// degrades to continued appending (reopening the path, falling back to the
+    /// previous handle) rather than leaving the sink closed and dropping records.
+    /// # Errors
+    /// - if the active path is unknown (no file sink configured)
+    /// - if any rename/remove/reopen fails (after the append fallback is restored)
+    fn rotate_file(&mut self) -> Res<()> {
+        let (_, keep) = self.rotate.ok_or("rotation not configured")?;
+        let path = self.file_path.clone().ok_or("no file path to rotate")?;
+
+        // release the live handle so the rename can proceed on every platform
+        let old = self.to_file.take();
+        match Self::shift_and_reopen(&path, keep) {
+            Ok(file) => {
+                self.to_file = Some(file);
+                self.file_len = 0;
+                // the live file is fresh and empty, so the dedup set must be
+                // reseeded from it rather than suppressing long-rotated lines
+                self.seen = None;
+                Ok(())
+            }
+            Err(err) => {
+                // keep logging: reopen the path in append mode, else reuse the
+                // handle we took, so the sink is never left permanently closed
+                self.to_file = fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+                    .ok()
+                    .or(old);
+                // the rename-away may already have succeeded, leaving `path` a
+                // fresh file; drop the dedup keys so archived lines aren't
+                // wrongly suppressed against content that isn't there anymore
+                self.seen = None;
+                self.file_len = self
+                    .to_file
+                    .as_ref()
+                    .and_then(|f| f.metadata().ok())
+                    .map_or(0, |m| m.len());
+                Err(err)
+            }
+        }
+    }
+}
+
+impl LogsyConf {
+    /// Resolves the effective filter for a log `target` (module path): the
+    /// longest directive prefix that matches on a path-segment boundary, or
+    /// the global default if none apply.
+    fn effective_filter(&self, target: &str) -> LevelFilter {
+        for (prefix, filter) in &self.filters {
+            if target == prefix
+                || (target.starts_with(prefix.as_str()) && target[prefix.len()..].starts_with("::"))
+            {
+                return *filter;
+            }
+        }
+        self.level
+            .map_or(LevelFilter::Off, |level| level.to_level_filter())
+    }
 }
 
 impl log::Log for Logsy {
     fn enabled(&self, metadata: &Metadata) -> bool {
         self.0
             .lock()
-            .is_ok_and(|mg| mg.level.is_some_and(|level| metadata.level() <= level))
+            .is_ok_and(|mg| metadata.level() <= mg.effective_filter(metadata.target()))
     }
 
     fn log(&self, record: &Record) {
@@ -54,25 +248,85 @@ impl log::Log for Logsy {
             Level::Error => AnsiColor::BrightRed,
         };
 
-        if conf.to_stderr {
+        if let Some(dest) = conf.console {
+            // coloring is a runtime decision: honor an explicit choice, else
+            // color only when the chosen stream is an interactive terminal
+            #[cfg(feature = "styled")]
+            let colored = match conf.color {
+                ColorChoice::Always => true,
+                ColorChoice::Never => false,
+                ColorChoice::Auto => conf.console_is_tty,
+            };
+
             #[cfg(feature = "styled")]
-            let [level_style, dim, italic] = {
-                [
+            let line = if colored {
+                let [level_style, dim, italic] = [
                     Style::new().fg_color(Some(Color::Ansi(color))).bold(),
                     Style::new().dimmed(),
                     Style::new().italic(),
-                ]
+                ];
+                let level = format!("{level_style}{:5}{level_style:#}", record.level());
+                let ts = format!("{italic}{ts}{italic:#}");
+                format!("{dim}[{ts}{level} {dim}{italic}{mod_p}{italic:#}{dim}]{dim:#} {msg}")
+            } else {
+                format!("[{ts}{:5} {mod_p}] {msg}", record.level())
             };
             #[cfg(not(feature = "styled"))]
-            let [level_style, dim, italic] = { [String::new(), String::new(), String::new()] };
-            let level = format!("{level_style}{:5}{level_style:#}", record.level());
+            let line = format!("[{ts}{:5} {mod_p}] {msg}", record.level());
 
-            let ts = format!("{italic}{ts}{italic:#}");
-            eprintln!("{dim}[{ts}{level} {dim}{italic}{mod_p}{italic:#}{dim}]{dim:#} {msg}");
+            match dest {
+                LogDestination::Stdout => println!("{line}"),
+                LogDestination::Stderr => eprintln!("{line}"),
+                LogDestination::File => {}
+            }
         }
         #[cfg(feature = "file")]
-        if let Some(file) = &mut conf.to_file {
-            let _ = writeln!(file, "[{ts}{:5} {mod_p}] {msg}", record.level());
+        if conf.to_file.is_some() {
+            // the text rendering doubles as the timestamp-independent dedup key,
+            // so dedup stays stable regardless of the selected output format
+            let text_line = format!("[{ts}{:5} {mod_p}] {msg}", record.level());
+            let deduped = conf.dedup
+                && !conf
+                    .seen
+                    .get_or_insert_with(Default::default)
+                    .insert(LogsyConf::strip_ts(&text_line));
+            if !deduped {
+                let line = match conf.format {
+                    LogFormat::Text => text_line,
+                    LogFormat::Json => {
+                        let mut obj = String::from("{");
+                        #[cfg(feature = "time")]
+                        obj.push_str(&format!("\"timestamp\":\"{}\",", json_escape(ts.trim_end())));
+                        obj.push_str(&format!("\"level\":\"{}\",", record.level()));
+                        obj.push_str(&format!("\"target\":\"{}\"", json_escape(mod_p)));
+                        if let Some(file) = record.file() {
+                            obj.push_str(&format!(",\"file\":\"{}\"", json_escape(file)));
+                        }
+                        if let Some(line) = record.line() {
+                            obj.push_str(&format!(",\"line\":{line}"));
+                        }
+                        obj.push_str(&format!(",\"message\":\"{}\"", json_escape(&msg.to_string())));
+                        obj.push('}');
+                        obj
+                    }
+                };
+                let line_len = line.len() as u64 + 1; // + 1 for the newline writeln! adds
+
+                if let Some((max_bytes, _)) = conf.rotate
+                    && conf.file_len > 0
+                    && conf.file_len + line_len > max_bytes
+                {
+                    // a rotation failure degrades to continued appending, never a dropped record
+                    if let Err(err) = conf.rotate_file() {
+                        eprintln!("ERROR: log rotation failed, continuing to append: {err}");
+                    }
+                }
+                if let Some(file) = &mut conf.to_file
+                    && writeln!(file, "{line}").is_ok()
+                {
+                    conf.file_len += line_len;
+                }
+            }
         }
     }
     fn flush(&self) {}
@@ -80,10 +334,27 @@ impl log::Log for Logsy {
 
 static LOGSY: Logsy = Logsy(Mutex::new(LogsyConf {
     installed: false,
-    to_stderr: false,
+    console: None,
+    #[cfg(feature = "styled")]
+    console_is_tty: false,
+    #[cfg(feature = "styled")]
+    color: ColorChoice::Auto,
     #[cfg(feature = "file")]
     to_file: None,
+    #[cfg(feature = "file")]
+    file_path: None,
+    #[cfg(feature = "file")]
+    rotate: None,
+    #[cfg(feature = "file")]
+    file_len: 0,
+    #[cfg(feature = "file")]
+    format: LogFormat::Text,
+    #[cfg(feature = "file")]
+    dedup: true,
+    #[cfg(feature = "file")]
+    seen: None,
     level: None,
+    filters: Vec::new(),
 }));
 
 /// checks whether it's already installed, does it so if not
@@ -97,15 +368,14 @@ fn ensure_installed() -> Res<()> {
         LOGSY.0.lock()?.installed = true;
         log::set_logger(&LOGSY).map_err(|e| e.to_string())?;
 
-        #[allow(unused_mut)] // is used if `env`
-        let mut log_level = LevelFilter::Info;
         #[cfg(feature = "env")]
         if let Ok(env_log_level) = std::env::var("RUST_LOG") {
-            log_level = LevelFilter::from_str(&env_log_level).unwrap_or_else(|err| {
+            try_set_filters(&env_log_level).unwrap_or_else(|err| {
                 panic!("{err}: invalid RUST_LOG env var value: {env_log_level:?}")
             });
+            return Ok(());
         }
-        try_set_level(log_level)?;
+        try_set_level(LevelFilter::Info)?;
     }
     Ok(())
 }
@@ -115,9 +385,7 @@ fn ensure_installed() -> Res<()> {
 /// - if can't `ensure_installed`
 /// - if can't access global state: can't lock mutex
 pub fn try_to_console() -> Res<()> {
-    ensure_installed()?;
-    LOGSY.0.lock()?.to_stderr = true;
-    Ok(())
+    try_set_destination(LogDestination::Stderr)
 }
 
 /// Start logging to `stderr`
@@ -127,6 +395,47 @@ pub fn to_console() {
     try_to_console().unwrap();
 }
 
+/// Try to start logging to a chosen console stream. The stream's terminal
+/// state is probed and cached so coloring (under `feature = "styled"`) can be a
+/// runtime decision; see [`try_set_color`].
+/// # Errors
+/// - if can't `ensure_installed`
+/// - if can't access global state: can't lock mutex
+pub fn try_set_destination(dest: LogDestination) -> Res<()> {
+    ensure_installed()?;
+    let mut conf = LOGSY.0.lock()?;
+    conf.console = Some(dest);
+    #[cfg(feature = "styled")]
+    {
+        conf.console_is_tty = stream_is_tty(dest);
+    }
+    Ok(())
+}
+
+/// Start logging to a chosen console stream.
+/// # Panics
+/// errors of [`try_set_destination`]
+pub fn set_destination(dest: LogDestination) {
+    try_set_destination(dest).unwrap();
+}
+
+/// Try to set when ANSI color is emitted on the console sink.
+/// # Errors
+/// if can't access global state: can't acquire mutex
+#[cfg(feature = "styled")]
+pub fn try_set_color(choice: ColorChoice) -> Res<()> {
+    LOGSY.0.lock()?.color = choice;
+    Ok(())
+}
+
+/// Set when ANSI color is emitted on the console sink.
+/// # Panics
+/// errors of [`try_set_color`]
+#[cfg(feature = "styled")]
+pub fn set_color(choice: ColorChoice) {
+    try_set_color(choice).unwrap();
+}
+
 /// Try to start logging to a specified file.\
 /// This function can be called again without restarting the app if you need
 /// (e.g. for implementing log rotation).\
@@ -150,10 +459,121 @@ pub fn try_to_file(path: impl AsRef<std::path::Path>, append: bool) -> Res<()> {
         .write(true)
         .append(append)
         .open(path.as_ref())?;
-    LOGSY.0.lock()?.to_file = Some(file);
+    let file_len = file.metadata().map(|m| m.len()).unwrap_or(0);
+
+    // reseed the dedup set from the file we're now pointing at, so repeats
+    // already present on disk are suppressed and a prior path's keys are dropped;
+    // JSON-format lines on disk never match a text key and so suppress nothing
+    let mut seen = std::collections::HashSet::new();
+    if let Ok(contents) = fs::read_to_string(path.as_ref()) {
+        for existing in contents.lines() {
+            seen.insert(LogsyConf::strip_ts(existing));
+        }
+    }
+
+    let mut conf = LOGSY.0.lock()?;
+    conf.to_file = Some(file);
+    conf.file_path = Some(path.as_ref().to_path_buf());
+    conf.rotate = None;
+    conf.file_len = file_len;
+    conf.seen = Some(seen);
     Ok(())
 }
 
+/// Try to start logging to a file that is rotated once it grows past
+/// `max_bytes`: the live `foo.log` is renamed to `foo.log.1`, existing
+/// archives are shifted up (`foo.log.{n}` → `foo.log.{n + 1}`) and anything
+/// past `keep` is deleted, then a fresh `foo.log` is opened.\
+/// The file is opened in append mode so an existing log is continued.
+/// # Errors
+/// errors of [`try_to_file`]
+#[cfg(feature = "file")]
+pub fn try_to_file_rotating(
+    path: impl AsRef<std::path::Path>,
+    max_bytes: u64,
+    keep: usize,
+) -> Res<()> {
+    try_to_file(path, true)?;
+    LOGSY.0.lock()?.rotate = Some((max_bytes, keep));
+    Ok(())
+}
+
+/// Start logging to a file with size-based rotation.
+/// # Panics
+/// errors of [`try_to_file_rotating`]
+#[cfg(feature = "file")]
+pub fn to_file_rotating(path: impl AsRef<std::path::Path>, max_bytes: u64, keep: usize) {
+    try_to_file_rotating(path, max_bytes, keep).unwrap();
+}
+
+/// Try to prune sibling log files in `dir` whose name starts with `prefix`,
+/// keeping only the `keep_newest` most recently modified and deleting the rest.\
+/// Useful for apps that create per-session files (e.g. `session_<key>.log`) and
+/// want to bound their accumulation without shipping their own cleanup.\
+/// Returns the number of files removed.
+/// # Errors
+/// - if `dir` can't be read
+/// - if an entry's metadata can't be read
+#[cfg(feature = "file")]
+pub fn try_prune_logs(
+    dir: impl AsRef<std::path::Path>,
+    prefix: &str,
+    keep_newest: usize,
+) -> Res<usize> {
+    let mut matching = Vec::new();
+    for entry in fs::read_dir(dir.as_ref())? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        if !entry.file_name().to_string_lossy().starts_with(prefix) {
+            continue;
+        }
+        let modified = entry
+            .metadata()?
+            .modified()
+            .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+        matching.push((modified, entry.path()));
+    }
+    // newest first, so everything past the keep count is the oldest
+    matching.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    let mut removed = 0;
+    for (_, path) in matching.into_iter().skip(keep_newest) {
+        if fs::remove_file(&path).is_ok() {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Prune stale log files, then start logging to `path`: the new file isn't
+/// among the pruned candidates since the cleanup runs before it is opened.
+/// # Errors
+/// - errors of [`try_prune_logs`]
+/// - errors of [`try_to_file`]
+#[cfg(feature = "file")]
+pub fn try_to_file_pruned(
+    path: impl AsRef<std::path::Path>,
+    prefix: &str,
+    keep_newest: usize,
+) -> Res<()> {
+    let dir = match path.as_ref().parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => std::path::Path::new("."),
+    };
+    try_prune_logs(dir, prefix, keep_newest)?;
+    try_to_file(path, true)
+}
+
+/// Prune stale log files, then start logging to `path`.
+/// # Panics
+/// errors of [`try_to_file_pruned`]
+#[cfg(feature = "file")]
+pub fn to_file_pruned(path: impl AsRef<std::path::Path>, prefix: &str, keep_newest: usize) {
+    try_to_file_pruned(path, prefix, keep_newest).unwrap();
+}
+
 /// Start logging to a specified file.\
 /// This function can be called again without restarting the app if you need
 /// (e.g. for implementing log rotation).\
@@ -170,10 +590,95 @@ pub fn to_file(path: impl AsRef<std::path::Path>, append: bool) {
 /// if can't access global state: can't acquire mutex
 pub fn try_set_level(filter: LevelFilter) -> Res<()> {
     log::set_max_level(filter);
-    LOGSY.0.lock()?.level = filter.to_level();
+    let mut conf = LOGSY.0.lock()?;
+    conf.level = filter.to_level();
+    conf.filters.clear();
+    Ok(())
+}
+
+/// Try to set per-target level filters from an env_logger-style directive
+/// string: comma-separated `target=level` overrides plus an optional bare
+/// `level` that sets the global default, e.g. `warn,my_crate::db=debug,hyper=off`.\
+/// Overrides are matched longest-prefix-first against a record's module path on
+/// path-segment boundaries. `log::set_max_level` is pinned to the most verbose
+/// directive so the `log` facade never short-circuits a record a module wants.
+/// # Errors
+/// - if a level token is not a valid [`LevelFilter`]
+/// - if can't access global state: can't acquire mutex
+pub fn try_set_filters(directives: &str) -> Res<()> {
+    let mut default = LevelFilter::Info;
+    let mut filters = Vec::new();
+    for part in directives.split(',') {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+        if let Some((target, level)) = part.split_once('=') {
+            filters.push((target.trim().to_owned(), LevelFilter::from_str(level.trim())?));
+        } else {
+            default = LevelFilter::from_str(part)?;
+        }
+    }
+    // longest prefix first so `enabled`/`log` pick the most specific directive
+    filters.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+
+    let max = filters
+        .iter()
+        .map(|(_, filter)| *filter)
+        .chain(std::iter::once(default))
+        .max()
+        .unwrap_or(default);
+    log::set_max_level(max);
+
+    let mut conf = LOGSY.0.lock()?;
+    conf.level = default.to_level();
+    conf.filters = filters;
     Ok(())
 }
 
+/// Set per-target level filters from a directive string.
+/// # Panics
+/// errors of [`try_set_filters`]
+pub fn set_filters(directives: &str) {
+    try_set_filters(directives).unwrap();
+}
+
+/// Try to set the file sink's output format (text or JSON lines).
+/// # Errors
+/// if can't access global state: can't acquire mutex
+#[cfg(feature = "file")]
+pub fn try_set_format(format: LogFormat) -> Res<()> {
+    LOGSY.0.lock()?.format = format;
+    Ok(())
+}
+
+/// Set the file sink's output format.
+/// # Panics
+/// errors of [`try_set_format`]
+#[cfg(feature = "file")]
+pub fn set_format(format: LogFormat) {
+    try_set_format(format).unwrap();
+}
+
+/// Try to toggle duplicate suppression for the file sink. When enabled (the
+/// default) a file line identical to an earlier one — ignoring the timestamp —
+/// is skipped; disable it to keep every repeat.
+/// # Errors
+/// if can't access global state: can't acquire mutex
+#[cfg(feature = "file")]
+pub fn try_set_dedup(dedup: bool) -> Res<()> {
+    LOGSY.0.lock()?.dedup = dedup;
+    Ok(())
+}
+
+/// Toggle duplicate suppression for the file sink.
+/// # Panics
+/// errors of [`try_set_dedup`]
+#[cfg(feature = "file")]
+pub fn set_dedup(dedup: bool) {
+    try_set_dedup(dedup).unwrap();
+}
+
 /// Set log level filter
 /// # Panics
 /// errors of [`try_set_level`]
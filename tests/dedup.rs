@@ -0,0 +1,35 @@
+use log::{info, LevelFilter};
+use tempdir::TempDir;
+
+fn count(path: &std::path::Path, needle: &str) -> usize {
+    std::fs::read_to_string(path).unwrap().matches(needle).count()
+}
+
+// logsy is a global singleton, so the dedup behaviours are exercised as one
+// sequential test rather than racing threads over the shared config.
+#[test]
+fn dedup_behaviour() {
+    let dir = TempDir::new("logsy_dedup").unwrap();
+
+    // identical lines are written only once
+    let path = dir.path().join("d.log");
+    logsy::try_to_file(&path, true).unwrap();
+    logsy::set_level(LevelFilter::Info);
+    info!("repeated diagnostic");
+    info!("repeated diagnostic");
+    assert_eq!(count(&path, "repeated diagnostic"), 1);
+
+    // reopening the same file seeds the dedup set from its contents, so a line
+    // already on disk is suppressed on the next identical write
+    logsy::try_to_file(&path, true).unwrap();
+    info!("repeated diagnostic");
+    assert_eq!(count(&path, "repeated diagnostic"), 1);
+
+    // opting out keeps every repeat
+    let path = dir.path().join("d2.log");
+    logsy::try_to_file(&path, true).unwrap();
+    logsy::set_dedup(false);
+    info!("kept diagnostic");
+    info!("kept diagnostic");
+    assert_eq!(count(&path, "kept diagnostic"), 2);
+}
@@ -0,0 +1,25 @@
+use log::{Level, Metadata};
+use tempdir::TempDir;
+
+#[test]
+fn per_module_directives_resolve_by_longest_prefix() {
+    // installing any sink installs the logger; then apply the directives
+    let dir = TempDir::new("logsy_filters").unwrap();
+    logsy::try_to_file(dir.path().join("f.log"), false).unwrap();
+    logsy::set_filters("warn,my_crate::db=debug,hyper=off");
+
+    let logger = log::logger();
+    let enabled =
+        |level, target| logger.enabled(&Metadata::builder().level(level).target(target).build());
+
+    // my_crate::db=debug applies to nested module paths
+    assert!(enabled(Level::Debug, "my_crate::db::pool"));
+    assert!(!enabled(Level::Trace, "my_crate::db::pool"));
+    // hyper=off silences everything under hyper
+    assert!(!enabled(Level::Error, "hyper::client"));
+    // the global default is warn
+    assert!(enabled(Level::Warn, "something::else"));
+    assert!(!enabled(Level::Info, "something::else"));
+    // a prefix only matches on a path-segment boundary, not mid-identifier
+    assert!(!enabled(Level::Debug, "my_crate::dbpool"));
+}
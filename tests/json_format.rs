@@ -0,0 +1,62 @@
+use log::{Level, LevelFilter, Record};
+use tempdir::TempDir;
+
+fn last_line(path: &std::path::Path) -> String {
+    let contents = std::fs::read_to_string(path).unwrap();
+    contents.lines().last().unwrap().to_owned()
+}
+
+#[test]
+fn json_format_emits_one_object_per_record() {
+    let dir = TempDir::new("logsy_json").unwrap();
+    let path = dir.path().join("j.log");
+    logsy::try_to_file(&path, true).unwrap();
+    logsy::set_level(LevelFilter::Trace);
+    logsy::set_dedup(false);
+    logsy::set_format(logsy::LogFormat::Json);
+
+    let logger = log::logger();
+
+    // a record carrying file and line
+    logger.log(
+        &Record::builder()
+            .level(Level::Info)
+            .target("my::target")
+            .module_path(Some("my::target"))
+            .file(Some("src/foo.rs"))
+            .line(Some(42))
+            .args(format_args!("plain message"))
+            .build(),
+    );
+    let line = last_line(&path);
+    assert!(line.starts_with('{') && line.ends_with('}'));
+    assert!(line.contains("\"level\":\"INFO\""));
+    assert!(line.contains("\"target\":\"my::target\""));
+    assert!(line.contains("\"file\":\"src/foo.rs\""));
+    assert!(line.contains("\"line\":42"));
+    assert!(line.contains("\"message\":\"plain message\""));
+
+    // a record without file/line omits those keys entirely
+    logger.log(
+        &Record::builder()
+            .level(Level::Warn)
+            .target("t2")
+            .args(format_args!("no location"))
+            .build(),
+    );
+    let line = last_line(&path);
+    assert!(!line.contains("\"file\""));
+    assert!(!line.contains("\"line\""));
+    assert!(line.contains("\"message\":\"no location\""));
+
+    // quotes, backslashes and control chars in the message are escaped
+    logger.log(
+        &Record::builder()
+            .level(Level::Error)
+            .target("t3")
+            .args(format_args!("a\"b\\c\nd"))
+            .build(),
+    );
+    let line = last_line(&path);
+    assert!(line.contains(r#""message":"a\"b\\c\nd""#));
+}
@@ -0,0 +1,28 @@
+use std::fs::File;
+use std::time::{Duration, SystemTime};
+use tempdir::TempDir;
+
+#[test]
+fn prune_removes_oldest_beyond_keep() {
+    let dir = TempDir::new("logsy_prune").unwrap();
+
+    // five session files with strictly increasing mtimes (0 = oldest)
+    let base = SystemTime::UNIX_EPOCH;
+    for i in 0..5u64 {
+        let f = File::create(dir.path().join(format!("session_{i}.log"))).unwrap();
+        f.set_modified(base + Duration::from_secs(i * 100)).unwrap();
+    }
+    // a non-matching file must be left alone
+    File::create(dir.path().join("notes.txt")).unwrap();
+
+    let removed = logsy::try_prune_logs(dir.path(), "session_", 2).unwrap();
+    assert_eq!(removed, 3);
+
+    // the two newest survive, the three oldest are gone, the stranger stays
+    assert!(!dir.path().join("session_0.log").exists());
+    assert!(!dir.path().join("session_1.log").exists());
+    assert!(!dir.path().join("session_2.log").exists());
+    assert!(dir.path().join("session_3.log").exists());
+    assert!(dir.path().join("session_4.log").exists());
+    assert!(dir.path().join("notes.txt").exists());
+}
@@ -0,0 +1,28 @@
+use log::{info, LevelFilter};
+use tempdir::TempDir;
+
+#[test]
+fn rotation_shifts_archive_and_keeps_logging() {
+    let dir = TempDir::new("logsy_rotation").unwrap();
+    let path = dir.path().join("foo.log");
+
+    logsy::set_level(LevelFilter::Info);
+    logsy::try_to_file_rotating(&path, 256, 3).unwrap();
+
+    // distinct messages so dedup doesn't swallow the repeats
+    for i in 0..100 {
+        info!("rotation line number {i}");
+    }
+
+    // the live file rotated at least once, producing foo.log.1
+    assert!(dir.path().join("foo.log.1").exists());
+
+    // the earliest line has been rotated out of the live file
+    let live = std::fs::read_to_string(&path).unwrap();
+    assert!(!live.contains("rotation line number 0"));
+
+    // logging still works after rotation — the sink must never be left closed
+    info!("after rotation marker");
+    let live = std::fs::read_to_string(&path).unwrap();
+    assert!(live.contains("after rotation marker"));
+}